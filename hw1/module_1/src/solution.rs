@@ -2,7 +2,7 @@ pub fn hello() -> &'static str {
     panic!("TODO: Complete this Code Segment");
 }
 
-pub fn is_leap_year(yr: i32) -> bool {
+pub fn is_leap_year<T: Into<i64>>(yr: T) -> bool {
     panic!("TODO: Complete this Code Segment");
 }
 
@@ -13,3 +13,9 @@ pub fn plus_one(n: &mut i32) {
 pub fn increase(n: &mut i32, incr: i32) {
     panic!("TODO: Complete this Code Segment");
 }
+
+pub fn increase_fetch(n: &mut i32, incr: i32) -> i32 {
+    let old = *n;
+    increase(n, incr);
+    old
+}
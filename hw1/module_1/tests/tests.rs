@@ -1,4 +1,4 @@
-use solution::{hello, increase, is_leap_year, plus_one};
+use solution::{hello, increase, increase_fetch, is_leap_year, plus_one};
 
 #[test]
 fn check_hello() {
@@ -20,6 +20,13 @@ fn check_leap_year3() {
     assert_eq!(is_leap_year(1900), false);
 }
 
+#[test]
+fn check_leap_year_generic() {
+    assert!(is_leap_year(2000u32));
+    assert_eq!(is_leap_year(2000u32), is_leap_year(2000i64));
+    assert_eq!(is_leap_year(1900u32), is_leap_year(1900i64));
+}
+
 #[test]
 fn test_incr() {
     let mut n: i32 = 9;
@@ -33,3 +40,10 @@ fn test_increase() {
     increase(&mut n, 3);
     assert_eq!(n, 12);
 }
+
+#[test]
+fn test_increase_fetch() {
+    let mut n: i32 = 9;
+    assert_eq!(increase_fetch(&mut n, 3), 9);
+    assert_eq!(n, 12);
+}
@@ -1,3 +1,8 @@
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, Write};
+
+#[derive(Serialize, Deserialize)]
 pub struct Animal<T>
 where
     T: AnimalBehavior,
@@ -11,10 +16,12 @@ pub trait AnimalBehavior {
     fn make_sound(&self) -> &str;
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Dog {
     pub breed: String,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct Parrot {
     pub color: String,
 }
@@ -26,3 +33,42 @@ impl AnimalBehavior for Dog {
 impl AnimalBehavior for Parrot {
     panic!("TODO: Complete this Code Segment");
 }
+
+/// A single animal type that can be any species, so that different
+/// species can share one collection
+#[derive(Serialize, Deserialize)]
+pub enum Species {
+    Dog { breed: String },
+    Parrot { color: String },
+}
+
+impl AnimalBehavior for Species {
+    fn make_sound(&self) -> &str {
+        match self {
+            Species::Dog { .. } => "Woof!",
+            Species::Parrot { .. } => "Polly wants a cracker!",
+        }
+    }
+}
+
+/// A roster of animals of mixed species that can be saved to and loaded from disk
+#[derive(Serialize, Deserialize)]
+pub struct Zoo {
+    pub animals: Vec<Animal<Species>>,
+}
+
+impl Zoo {
+    /// Write the zoo to `filename` as JSON
+    pub fn save(&self, filename: &str) -> Result<(), Error> {
+        let f = File::create(filename)?;
+        let mut writer = BufWriter::new(f);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()
+    }
+
+    /// Read a zoo previously written by `save`
+    pub fn load(filename: &str) -> Zoo {
+        let f = File::open(filename).expect("error while opening file");
+        serde_json::from_reader(BufReader::new(f)).expect("error while parsing zoo")
+    }
+}
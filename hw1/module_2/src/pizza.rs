@@ -4,7 +4,19 @@ Result and Option
 - Create a Pizza struct that has a vector of Toppings
 */
 
-#[derive(Clone, Copy, PartialEq)]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "pricing")]
+use std::cmp::Ordering;
+#[cfg(feature = "pricing")]
+use std::collections::BTreeMap;
+#[cfg(feature = "pricing")]
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Write};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Toppings {
     Onion,
     Sausage,
@@ -14,22 +26,114 @@ pub enum Toppings {
     Oreos,
 }
 
+impl fmt::Display for Toppings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Toppings::Onion => "Onion",
+            Toppings::Sausage => "Sausage",
+            Toppings::Pineapple => "Pineapple",
+            Toppings::Spinach => "Spinach",
+            Toppings::Cheetos => "Cheetos",
+            Toppings::Oreos => "Oreos",
+        };
+        f.pad(name)
+    }
+}
+
+/// Price of a pizza with no toppings, in cents
+#[cfg(feature = "pricing")]
+pub const BASE_PRICE: u32 = 1000;
+
+#[cfg(feature = "pricing")]
+impl Toppings {
+    /// Price of adding this topping, in cents
+    pub fn price(&self) -> u32 {
+        match self {
+            Toppings::Onion => 50,
+            Toppings::Sausage => 150,
+            Toppings::Pineapple => 100,
+            Toppings::Spinach => 75,
+            Toppings::Cheetos => 125,
+            Toppings::Oreos => 125,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Pizza {
     toppings: Vec<Toppings>,
+    /// The most toppings this pizza can hold. None means unbounded.
+    capacity: Option<usize>,
 }
 
 impl Pizza {
-    /// Create a new empty pizza
+    /// Create a new empty pizza with no limit on the number of toppings
     pub fn new() -> Self {
         panic!("TODO: Complete this Code Segment");
     }
 
+    /// Create a new empty pizza that can hold at most `max` toppings
+    pub fn with_capacity(max: usize) -> Self {
+        Pizza {
+            toppings: Vec::new(),
+            capacity: Some(max),
+        }
+    }
+
     /// Add this topping to the pizza
     /// Return/throw a PizzaError if the topping is not valid according to check_topping
+    /// or if the pizza is already full
     pub fn add_topping(&mut self, topping: Toppings) -> Result<(), PizzaError> {
+        if self.is_full() {
+            return Err(PizzaError);
+        }
         panic!("TODO: Complete this Code Segment");
     }
 
+    /// Check if the pizza has reached its capacity
+    pub fn is_full(&self) -> bool {
+        match self.capacity {
+            Some(max) => self.toppings.len() >= max,
+            None => false,
+        }
+    }
+
+    /// Collect the allergens across every topping on the pizza
+    pub fn allergens(&self) -> HashSet<Allergen> {
+        self.toppings
+            .iter()
+            .flat_map(|t| topping_allergens(t).iter().copied())
+            .collect()
+    }
+
+    /// The distinct toppings found on both this pizza and the other one
+    pub fn shared_toppings(&self, other: &Pizza) -> HashSet<Toppings> {
+        let mine: HashSet<Toppings> = self.toppings.iter().copied().collect();
+        other
+            .toppings
+            .iter()
+            .copied()
+            .filter(|t| mine.contains(t))
+            .collect()
+    }
+
+    /// The distinct toppings found on either this pizza or the other one
+    pub fn combined_toppings(&self, other: &Pizza) -> HashSet<Toppings> {
+        self.toppings
+            .iter()
+            .chain(other.toppings.iter())
+            .copied()
+            .collect()
+    }
+
+    /// Remove one occurrence of this topping from the pizza.
+    /// Return how many of it remain, or none if it was never added
+    pub fn remove_topping(&mut self, topping: &Toppings) -> Option<usize> {
+        let idx = self.toppings.iter().position(|t| t == topping)?;
+        self.toppings.remove(idx);
+        Some(self.toppings.iter().filter(|t| *t == topping).count())
+    }
+
     /// See if this pizza has a topping and how many times it was added.
     /// Return none if it was never added
     pub fn has_topping(&self, topping: &Toppings) -> Option<usize> {
@@ -37,6 +141,112 @@ impl Pizza {
     }
 }
 
+/// How many times each topping appears
+#[cfg(feature = "pricing")]
+fn topping_counts(toppings: &[Toppings]) -> HashMap<Toppings, usize> {
+    let mut counts = HashMap::new();
+    for topping in toppings {
+        *counts.entry(*topping).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[cfg(feature = "pricing")]
+impl Pizza {
+    /// Total price of the pizza in cents: the base price plus every topping
+    pub fn price(&self) -> u32 {
+        BASE_PRICE + self.toppings.iter().map(Toppings::price).sum::<u32>()
+    }
+
+    /// Format a receipt: the base price, then one line per distinct topping
+    /// (sorted by name) with its count and total price, then the overall total.
+    /// Prices are right-aligned so the columns line up.
+    pub fn receipt(&self) -> String {
+        let mut counts: BTreeMap<String, (usize, u32)> = BTreeMap::new();
+        for topping in &self.toppings {
+            let line = counts.entry(topping.to_string()).or_insert((0, 0));
+            line.0 += 1;
+            line.1 += topping.price();
+        }
+
+        let mut receipt = receipt_line("Base", "", BASE_PRICE);
+        for (name, (count, price)) in &counts {
+            receipt.push_str(&receipt_line(name, &format!("x{}", count), *price));
+        }
+        receipt.push_str(&"-".repeat(RECEIPT_WIDTH));
+        receipt.push('\n');
+        receipt.push_str(&receipt_line("Total", "", self.price()));
+        receipt
+    }
+}
+
+/// Width of each line of a receipt, not counting the newline
+#[cfg(feature = "pricing")]
+const RECEIPT_WIDTH: usize = 28;
+
+/// One receipt line: the item name, its count (if any), and a price in cents shown as dollars
+#[cfg(feature = "pricing")]
+fn receipt_line(name: &str, count: &str, cents: u32) -> String {
+    let price = format!("${}.{:02}", cents / 100, cents % 100);
+    format!("{:<14}{:>4}{:>10}\n", name, count, price)
+}
+
+/// Pizzas are ordered by price, and pizzas with the same price by how many
+/// toppings they have. Pizzas that tie on both rank the same if they hold the
+/// same toppings, whatever their order or capacity, even though `==` still
+/// tells them apart. Pizzas that tie but hold different toppings (say Onion
+/// and Pineapple against two Spinach) have no order, so neither `<=` nor `>=`
+/// holds between them.
+#[cfg(feature = "pricing")]
+impl PartialOrd for Pizza {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        let by_price = self.price().cmp(&other.price());
+        match by_price.then(self.toppings.len().cmp(&other.toppings.len())) {
+            Ordering::Equal
+                if topping_counts(&self.toppings) != topping_counts(&other.toppings) =>
+            {
+                None
+            }
+            ordering => Some(ordering),
+        }
+    }
+}
+
+/// Write a whole order of pizzas to `filename` as JSON
+pub fn save_order(pizzas: &[Pizza], filename: &str) -> Result<(), Error> {
+    let f = File::create(filename)?;
+    let mut writer = BufWriter::new(f);
+    serde_json::to_writer(&mut writer, pizzas)?;
+    writer.flush()
+}
+
+/// Read an order written by save_order.
+/// Return an error if any pizza in the file has a topping that fails check_topping
+pub fn load_order(filename: &str) -> Result<Vec<Pizza>, Error> {
+    let f = File::open(filename)?;
+    let pizzas: Vec<Pizza> = serde_json::from_reader(BufReader::new(f))?;
+    for pizza in &pizzas {
+        if pizza.capacity.is_some_and(|max| pizza.toppings.len() > max) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "order contains a pizza with {} toppings over its capacity",
+                    pizza.toppings.len()
+                ),
+            ));
+        }
+        for topping in &pizza.toppings {
+            check_topping(topping).map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("order contains invalid topping {:?}", topping),
+                )
+            })?;
+        }
+    }
+    Ok(pizzas)
+}
+
 /// A simple empty struct to indicate an error. This could be an enum and could hold data.
 #[derive(Debug, Clone)]
 pub struct PizzaError;
@@ -51,3 +261,23 @@ pub fn check_topping(topping: &Toppings) -> Result<(), PizzaError> {
         _ => Err(PizzaError {}),
     }
 }
+
+/// Common allergens a topping can carry
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Allergen {
+    Dairy,
+    Gluten,
+    Soy,
+}
+
+/// The allergens found in a topping. Every topping is listed so new ones must be classified.
+pub fn topping_allergens(topping: &Toppings) -> &'static [Allergen] {
+    match topping {
+        Toppings::Onion => &[],
+        Toppings::Sausage => &[],
+        Toppings::Pineapple => &[],
+        Toppings::Spinach => &[],
+        Toppings::Cheetos => &[Allergen::Dairy],
+        Toppings::Oreos => &[Allergen::Gluten, Allergen::Soy],
+    }
+}
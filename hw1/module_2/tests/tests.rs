@@ -3,6 +3,8 @@ use module_2::animal::*;
 use module_2::coin::*;
 use module_2::pizza::*;
 use module_2::rectangle::*;
+use std::collections::HashSet;
+use std::fs;
 
 #[test]
 fn test_rect_struct() {
@@ -77,6 +79,191 @@ fn test_has_topping() {
     assert_eq!(0, za.has_topping(&Toppings::Spinach).unwrap());
 }
 
+#[test]
+fn test_pizza_capacity() {
+    let mut za = Pizza::with_capacity(2);
+    assert!(za.add_topping(Toppings::Onion).is_ok());
+    assert!(za.add_topping(Toppings::Spinach).is_ok());
+    assert!(za.is_full());
+    assert!(za.add_topping(Toppings::Sausage).is_err());
+    assert_eq!(None, za.has_topping(&Toppings::Sausage));
+}
+
+#[test]
+fn test_remove_topping() {
+    let mut za = Pizza::new();
+    assert!(za.add_topping(Toppings::Sausage).is_ok());
+    assert!(za.add_topping(Toppings::Sausage).is_ok());
+    assert_eq!(Some(1), za.remove_topping(&Toppings::Sausage));
+    assert_eq!(Some(1), za.has_topping(&Toppings::Sausage));
+    assert_eq!(None, za.remove_topping(&Toppings::Onion));
+}
+
+#[test]
+fn test_allergens() {
+    assert_eq!(topping_allergens(&Toppings::Cheetos), &[Allergen::Dairy]);
+    assert_eq!(
+        topping_allergens(&Toppings::Oreos),
+        &[Allergen::Gluten, Allergen::Soy]
+    );
+
+    let mut za = Pizza::new();
+    assert!(za.add_topping(Toppings::Sausage).is_ok());
+    assert!(za.add_topping(Toppings::Pineapple).is_ok());
+    assert_eq!(za.allergens(), HashSet::new());
+
+    // add_topping rejects these toppings, so build the pizza from JSON instead
+    let snack: Pizza =
+        serde_json::from_str(r#"{"toppings":["Cheetos","Oreos","Oreos"],"capacity":null}"#)
+            .unwrap();
+    assert_eq!(
+        snack.allergens(),
+        HashSet::from([Allergen::Dairy, Allergen::Gluten, Allergen::Soy])
+    );
+}
+
+#[test]
+fn test_shared_and_combined_toppings() {
+    let mut first = Pizza::new();
+    assert!(first.add_topping(Toppings::Sausage).is_ok());
+    assert!(first.add_topping(Toppings::Sausage).is_ok());
+    assert!(first.add_topping(Toppings::Onion).is_ok());
+    let mut second = Pizza::new();
+    assert!(second.add_topping(Toppings::Onion).is_ok());
+    assert!(second.add_topping(Toppings::Spinach).is_ok());
+
+    assert_eq!(
+        first.shared_toppings(&second),
+        HashSet::from([Toppings::Onion])
+    );
+    assert_eq!(
+        first.combined_toppings(&second),
+        HashSet::from([Toppings::Sausage, Toppings::Onion, Toppings::Spinach])
+    );
+
+    let empty = Pizza::new();
+    assert!(first.shared_toppings(&empty).is_empty());
+    assert_eq!(
+        empty.combined_toppings(&second),
+        HashSet::from([Toppings::Onion, Toppings::Spinach])
+    );
+}
+
+#[cfg(feature = "pricing")]
+#[test]
+fn test_pizza_price_ordering() {
+    let mut one = Pizza::new();
+    assert!(one.add_topping(Toppings::Sausage).is_ok());
+    let mut three = Pizza::new();
+    assert!(three.add_topping(Toppings::Onion).is_ok());
+    assert!(three.add_topping(Toppings::Onion).is_ok());
+    assert!(three.add_topping(Toppings::Onion).is_ok());
+    let mut pricey = Pizza::new();
+    assert!(pricey.add_topping(Toppings::Sausage).is_ok());
+    assert!(pricey.add_topping(Toppings::Pineapple).is_ok());
+
+    // Same price, so the pizza with more toppings is greater
+    assert_eq!(one.price(), three.price());
+    assert!(one < three);
+    assert!(three < pricey);
+    assert!(pricey > one);
+}
+
+#[cfg(feature = "pricing")]
+#[test]
+fn test_pizza_price_ordering_ties() {
+    // The same toppings in a different order, or a different capacity, rank the same
+    let mut a = Pizza::new();
+    assert!(a.add_topping(Toppings::Onion).is_ok());
+    assert!(a.add_topping(Toppings::Sausage).is_ok());
+    let mut b = Pizza::with_capacity(5);
+    assert!(b.add_topping(Toppings::Sausage).is_ok());
+    assert!(b.add_topping(Toppings::Onion).is_ok());
+    assert!(a <= b);
+    assert!(a >= b);
+    assert_ne!(a, b);
+    let (empty, empty_capped) = (Pizza::new(), Pizza::with_capacity(5));
+    assert!(empty <= empty_capped);
+    assert!(empty >= empty_capped);
+
+    // Same price and count but different toppings have no order
+    let mut c = Pizza::new();
+    assert!(c.add_topping(Toppings::Onion).is_ok());
+    assert!(c.add_topping(Toppings::Pineapple).is_ok());
+    let mut d = Pizza::new();
+    assert!(d.add_topping(Toppings::Spinach).is_ok());
+    assert!(d.add_topping(Toppings::Spinach).is_ok());
+    assert_eq!(c.price(), d.price());
+    assert_eq!(c.partial_cmp(&d), None);
+}
+
+#[cfg(feature = "pricing")]
+#[test]
+fn test_pizza_receipt() {
+    let mut za = Pizza::new();
+    assert!(za.add_topping(Toppings::Sausage).is_ok());
+    assert!(za.add_topping(Toppings::Onion).is_ok());
+    assert!(za.add_topping(Toppings::Onion).is_ok());
+    let expected = "\
+Base                  $10.00
+Onion           x2     $1.00
+Sausage         x1     $1.50
+----------------------------
+Total                 $12.50
+";
+    assert_eq!(za.receipt(), expected);
+
+    let empty = Pizza::new();
+    let expected = "\
+Base                  $10.00
+----------------------------
+Total                 $10.00
+";
+    assert_eq!(empty.receipt(), expected);
+}
+
+#[test]
+fn test_save_load_order() {
+    let mut first = Pizza::new();
+    assert!(first.add_topping(Toppings::Onion).is_ok());
+    assert!(first.add_topping(Toppings::Onion).is_ok());
+    let mut second = Pizza::with_capacity(3);
+    assert!(second.add_topping(Toppings::Pineapple).is_ok());
+    let order = vec![first, second];
+
+    let filename = "order_test.json";
+    save_order(&order, filename).unwrap();
+    let loaded = load_order(filename).unwrap();
+    assert_eq!(loaded, order);
+    assert_eq!(Some(2), loaded[0].has_topping(&Toppings::Onion));
+
+    let empty_filename = "order_empty_test.json";
+    save_order(&[], empty_filename).unwrap();
+    assert!(load_order(empty_filename).unwrap().is_empty());
+}
+
+#[test]
+fn test_load_order_rejects_invalid_topping() {
+    let filename = "order_invalid_test.json";
+    fs::write(
+        filename,
+        r#"[{"toppings":["Sausage","Oreos"],"capacity":null}]"#,
+    )
+    .unwrap();
+    assert!(load_order(filename).is_err());
+}
+
+#[test]
+fn test_load_order_rejects_over_capacity() {
+    let filename = "order_over_capacity_test.json";
+    fs::write(
+        filename,
+        r#"[{"toppings":["Onion","Onion","Onion"],"capacity":1}]"#,
+    )
+    .unwrap();
+    assert!(load_order(filename).is_err());
+}
+
 #[test]
 fn test_generics_dog() {
     let dog = Animal {
@@ -102,3 +289,37 @@ fn test_generics_bird() {
 
     assert_eq!(parrot.animal_type.make_sound(), "Polly wants a cracker!");
 }
+
+#[test]
+fn test_zoo_save_load() {
+    let zoo = Zoo {
+        animals: vec![
+            Animal {
+                name: String::from("Lassie"),
+                animal_type: Species::Dog {
+                    breed: String::from("Collie"),
+                },
+                weight: 10,
+            },
+            Animal {
+                name: String::from("Polly"),
+                animal_type: Species::Parrot {
+                    color: String::from("Green"),
+                },
+                weight: 1,
+            },
+        ],
+    };
+    let filename = "zoo_test.json";
+    zoo.save(filename).unwrap();
+
+    let loaded = Zoo::load(filename);
+    assert_eq!(loaded.animals.len(), 2);
+    assert_eq!(loaded.animals[0].name, "Lassie");
+    assert_eq!(loaded.animals[0].animal_type.make_sound(), "Woof!");
+    assert_eq!(loaded.animals[1].name, "Polly");
+    assert_eq!(
+        loaded.animals[1].animal_type.make_sound(),
+        "Polly wants a cracker!"
+    );
+}
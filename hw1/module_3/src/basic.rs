@@ -1,3 +1,5 @@
+use std::num::ParseIntError;
+
 // Serializes an integer into a string
 // 1. what's the difference between casting into a string and serializing into a string?
 pub fn serialize_to_string(data: u32) -> String {
@@ -13,3 +15,36 @@ pub fn serialize_to_bytes(data: u32) -> [u8; 4] {
 pub fn deserialize_from_bytes(bytes: [u8; 4]) -> u32 {
     panic!("TODO: Complete this Code Segment");
 }
+
+/// Number bases serialize_to_string_radix can write integers in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Radix {
+    Dec,
+    Hex,
+    Bin,
+    Oct,
+}
+
+/// Serializes an integer into a string in the given base. Every base except
+/// decimal gets a prefix ("0x", "0b", "0o") so the base can be recovered.
+pub fn serialize_to_string_radix(data: u32, radix: Radix) -> String {
+    match radix {
+        Radix::Dec => format!("{}", data),
+        Radix::Hex => format!("{:#x}", data),
+        Radix::Bin => format!("{:#b}", data),
+        Radix::Oct => format!("{:#o}", data),
+    }
+}
+
+/// Parses a string written by serialize_to_string_radix, using its prefix to pick the base
+pub fn deserialize_from_string_radix(string_data: &str) -> Result<u32, ParseIntError> {
+    if let Some(digits) = string_data.strip_prefix("0x") {
+        u32::from_str_radix(digits, 16)
+    } else if let Some(digits) = string_data.strip_prefix("0b") {
+        u32::from_str_radix(digits, 2)
+    } else if let Some(digits) = string_data.strip_prefix("0o") {
+        u32::from_str_radix(digits, 8)
+    } else {
+        string_data.parse()
+    }
+}
@@ -1,8 +1,13 @@
+use regex::Regex;
 use std::collections::HashMap;
+use std::collections::hash_map::Entry;
 use std::fs::File;
 use std::io::Error;
+use std::io::ErrorKind;
 use std::io::{BufReader, Read, Write};
 
+use crate::util::WriteMode;
+
 pub fn serialize_data_to_disk(data: HashMap<String, i32>, filename: &str) -> Result<(), Error> {
     panic!("TODO: Complete this Code Segment");
 }
@@ -10,3 +15,90 @@ pub fn serialize_data_to_disk(data: HashMap<String, i32>, filename: &str) -> Res
 pub fn deserialize_data_from_disk(filename: &str) -> HashMap<String, i32> {
     panic!("TODO: Complete this Code Segment");
 }
+
+/// Loads a map with deserialize_data_from_disk and re-keys it in a single pass,
+/// moving each entry into the result under `key_fn(key)`. The file format is
+/// owned by that function, so the entries are read through it rather than
+/// re-keyed while decoding. When several keys map to the same new key, the
+/// last one wins, where "last" means the entry whose original key sorts last
+/// rather than the last one read: the loaded map has no stable order, and this
+/// keeps the result the same from run to run.
+pub fn deserialize_data_rekeyed(
+    filename: &str,
+    key_fn: impl Fn(&str) -> String,
+) -> Result<HashMap<String, i32>, Error> {
+    let mut rekeyed: HashMap<String, i32> = HashMap::new();
+    // The original key behind each entry of `rekeyed`, to settle collisions
+    let mut sources: HashMap<String, String> = HashMap::new();
+    for (key, value) in deserialize_data_from_disk(filename) {
+        let new_key = key_fn(&key);
+        match sources.entry(new_key.clone()) {
+            Entry::Occupied(mut source) => {
+                if key > *source.get() {
+                    source.insert(key);
+                    rekeyed.insert(new_key, value);
+                }
+            }
+            Entry::Vacant(source) => {
+                source.insert(key);
+                rekeyed.insert(new_key, value);
+            }
+        }
+    }
+    Ok(rekeyed)
+}
+
+/// Lists the keys of a serialized map that do not match `pattern`, in sorted
+/// order; an empty list means every key is valid. The pattern is not anchored,
+/// so use `^...$` to require the whole key to match. A pattern that is not a
+/// valid regex is reported as an `ErrorKind::InvalidInput` error.
+pub fn validate_keys(filename: &str, pattern: &str) -> Result<Vec<String>, Error> {
+    let re = Regex::new(pattern).map_err(|e| Error::new(ErrorKind::InvalidInput, e))?;
+    let mut invalid: Vec<String> = deserialize_data_from_disk(filename)
+        .into_keys()
+        .filter(|key| !re.is_match(key))
+        .collect();
+    invalid.sort();
+    Ok(invalid)
+}
+
+/// Clamps every value in the map into `min..=max`, leaving values already in range alone.
+/// Panics if `min > max`.
+pub fn clamp_values(map: &mut HashMap<String, i32>, min: i32, max: i32) {
+    assert!(
+        min <= max,
+        "clamp range is empty: min {} > max {}",
+        min,
+        max
+    );
+    for value in map.values_mut() {
+        *value = (*value).clamp(min, max);
+    }
+}
+
+/// Deserializes a map like deserialize_data_from_disk and clamps its values into
+/// `min..=max`. An empty range (`min > max`) is an `ErrorKind::InvalidInput` error.
+pub fn load_and_clamp(filename: &str, min: i32, max: i32) -> Result<HashMap<String, i32>, Error> {
+    if min > max {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("clamp range is empty: min {} > max {}", min, max),
+        ));
+    }
+    let mut map = deserialize_data_from_disk(filename);
+    clamp_values(&mut map, min, max);
+    Ok(map)
+}
+
+/// Same as serialize_data_to_disk, but with `WriteMode::FailIfExists`
+/// an existing file is never overwritten.
+pub fn serialize_data_to_disk_with_mode(
+    data: HashMap<String, i32>,
+    filename: &str,
+    mode: WriteMode,
+) -> Result<(), Error> {
+    let claim = mode.claim(filename)?;
+    serialize_data_to_disk(data, filename)?;
+    claim.keep();
+    Ok(())
+}
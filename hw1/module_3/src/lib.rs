@@ -1,4 +1,6 @@
 pub mod basic;
 pub mod hashmap;
 pub mod structure;
+pub mod testdata;
+pub mod util;
 pub mod vector;
@@ -1,5 +1,11 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
 use std::fs::File;
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Write};
+
+use crate::util::WriteMode;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct University {
@@ -10,6 +16,145 @@ pub struct University {
     pub acceptance_rate: f32,
 }
 
+/// Reasons a change to a University was rejected
+#[derive(Debug, Clone, PartialEq)]
+pub enum StructureError {
+    /// The school name at this position in the batch was blank
+    EmptySchoolName { index: usize },
+    /// The school name at this position in the batch was already present (ignoring case)
+    DuplicateSchool { index: usize, name: String },
+}
+
+impl fmt::Display for StructureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StructureError::EmptySchoolName { index } => {
+                write!(f, "school name at index {} is empty", index)
+            }
+            StructureError::DuplicateSchool { index, name } => {
+                write!(
+                    f,
+                    "school name at index {} ({}) is a duplicate",
+                    index, name
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for StructureError {}
+
+impl University {
+    /// Formats the acceptance rate as a percentage with one decimal place, e.g. "7.0%"
+    pub fn acceptance_rate_display(&self) -> String {
+        format!("{:.1}%", self.acceptance_rate as f64 * 100.0)
+    }
+
+    /// Borrows each school name in order without cloning any of them
+    pub fn school_names(&self) -> impl Iterator<Item = &str> {
+        self.schools.iter().map(String::as_str)
+    }
+
+    /// Borrows the name of the school at index `i`, if there is one
+    pub fn school_at(&self, i: usize) -> Option<&str> {
+        self.schools.get(i).map(String::as_str)
+    }
+
+    /// Adds each school in order, rejecting blank names and names already present
+    /// (ignoring case). Stops at the first bad name and reports its index; the
+    /// schools before it stay added. Returns how many schools were added.
+    pub fn add_schools(&mut self, names: &[&str]) -> Result<usize, StructureError> {
+        let mut seen = self.school_keys();
+        for (index, name) in names.iter().enumerate() {
+            if name.trim().is_empty() {
+                return Err(StructureError::EmptySchoolName { index });
+            }
+            if !seen.insert(name.to_lowercase()) {
+                return Err(StructureError::DuplicateSchool {
+                    index,
+                    name: name.to_string(),
+                });
+            }
+            self.schools.push(name.to_string());
+        }
+        Ok(names.len())
+    }
+
+    /// Appends the schools of `other` that this University does not already
+    /// have (ignoring case), keeping their order. Merging twice changes nothing.
+    pub fn merge_schools_from(&mut self, other: &University) {
+        let mut seen = self.school_keys();
+        for school in &other.schools {
+            if seen.insert(school.to_lowercase()) {
+                self.schools.push(school.clone());
+            }
+        }
+    }
+
+    /// Lowercased school names, for comparing schools without regard to case
+    fn school_keys(&self) -> HashSet<String> {
+        self.schools.iter().map(|s| s.to_lowercase()).collect()
+    }
+
+    /// Each school's share of total enrollment. Enrollment is not tracked per
+    /// school yet, so it is split evenly; the shares sum to 1.0 unless there
+    /// are no schools, in which case the result is empty.
+    pub fn enrollment_shares(&self) -> Vec<(String, f64)> {
+        if self.schools.is_empty() {
+            return Vec::new();
+        }
+        let share = 1.0 / self.schools.len() as f64;
+        self.schools
+            .iter()
+            .map(|school| (school.clone(), share))
+            .collect()
+    }
+
+    /// Checks every field and reports all the problems found, not just the first
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+        if self.name.trim().is_empty() {
+            problems.push(String::from("name is empty"));
+        }
+        if !(0.0..=1.0).contains(&self.acceptance_rate) {
+            problems.push(format!(
+                "acceptance_rate {} is not between 0 and 1",
+                self.acceptance_rate
+            ));
+        }
+        let mut seen = HashSet::new();
+        for (i, school) in self.schools.iter().enumerate() {
+            if school.trim().is_empty() {
+                problems.push(format!("school {} is empty", i));
+            } else if !seen.insert(school.to_lowercase()) {
+                problems.push(format!("school {} ({}) is a duplicate", i, school));
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// Parses and validates each JSON file without building anything from them or
+/// writing anywhere. Returns every file with either Ok or all of its problems.
+pub fn validate_import(paths: &[&str]) -> Vec<(String, Result<(), Vec<String>>)> {
+    paths
+        .iter()
+        .map(|&path| (path.to_string(), validate_file(path)))
+        .collect()
+}
+
+fn validate_file(path: &str) -> Result<(), Vec<String>> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| vec![format!("could not read file: {}", e)])?;
+    let university: University = serde_json::from_str(&contents)
+        .map_err(|e| vec![format!("could not parse file: {}", e)])?;
+    university.validate()
+}
+
 pub fn serialize_struct_to_jsonstring(struct_data: &University) -> String {
     panic!("TODO: Complete this Code Segment");
 }
@@ -18,6 +163,26 @@ pub fn deserialize_jsonstring_to_struct(string_data: &str) -> University {
     panic!("TODO: Complete this Code Segment");
 }
 
+/// A University together with whatever top-level fields it did not recognize.
+/// Flattening the struct first lets it claim its own fields, leaving the rest for `unknown`.
+#[derive(Deserialize)]
+struct UniversityWithUnknown {
+    #[serde(flatten)]
+    university: University,
+    #[serde(flatten)]
+    unknown: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Deserializes a University from JSON, tolerating extra top-level fields and
+/// returning their names (in sorted order) alongside the struct.
+pub fn deserialize_jsonstring_capturing_unknown(
+    string_data: &str,
+) -> Result<(University, Vec<String>), serde_json::Error> {
+    let parsed: UniversityWithUnknown = serde_json::from_str(string_data)?;
+    let unknown = parsed.unknown.into_iter().map(|(key, _)| key).collect();
+    Ok((parsed.university, unknown))
+}
+
 pub fn serialize_struct_to_cbor(struct_data: &University, filename: &str) {
     panic!("TODO: Complete this Code Segment");
 }
@@ -25,3 +190,119 @@ pub fn serialize_struct_to_cbor(struct_data: &University, filename: &str) {
 pub fn deserialize_struct_from_cbor(filename: &str) -> University {
     panic!("TODO: Complete this Code Segment");
 }
+
+/// The formats a University can be serialized to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Json,
+    Cbor,
+    MessagePack,
+}
+
+impl Format {
+    /// Every format, in the order smallest_format prefers them when sizes tie
+    pub const ALL: [Format; 3] = [Format::Json, Format::Cbor, Format::MessagePack];
+
+    /// Serializes the struct into this format in memory
+    pub fn encode(self, struct_data: &University) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.encode_into(&mut bytes, struct_data)
+            .expect("error encoding struct");
+        bytes
+    }
+
+    /// Serializes the struct into this format through `writer`. This is the one
+    /// encoder per format, used both in memory and by the file serializers.
+    pub fn encode_into(
+        self,
+        writer: &mut impl Write,
+        struct_data: &University,
+    ) -> Result<(), Error> {
+        match self {
+            Format::Json => serde_json::to_writer(writer, struct_data)?,
+            Format::Cbor => serde_cbor::to_writer(writer, struct_data).map_err(Error::other)?,
+            Format::MessagePack => {
+                rmp_serde::encode::write(writer, struct_data).map_err(Error::other)?
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Serializes the struct to every format in memory (nothing touches the disk)
+/// and returns the format with the fewest bytes along with that byte count.
+/// Ties go to whichever format comes first in `Format::ALL`.
+pub fn smallest_format(struct_data: &University) -> (Format, usize) {
+    Format::ALL
+        .iter()
+        .map(|&format| (format, format.encode(struct_data).len()))
+        .min_by_key(|&(_, size)| size)
+        .expect("there is at least one format")
+}
+
+/// Checks that a CBOR file holds exactly one complete University: false if the
+/// data stops part way through the struct or if bytes follow it. Data that is
+/// not valid CBOR for a University at all is an `ErrorKind::InvalidData` error.
+pub fn is_cbor_complete(filename: &str) -> Result<bool, Error> {
+    let bytes = fs::read(filename)?;
+    let mut deserializer = serde_cbor::Deserializer::from_slice(&bytes);
+    match University::deserialize(&mut deserializer) {
+        Ok(_) => Ok(deserializer.end().is_ok()),
+        Err(e) if e.is_eof() => Ok(false),
+        Err(e) => Err(Error::new(ErrorKind::InvalidData, e)),
+    }
+}
+
+/// Same as serialize_struct_to_cbor, but with `WriteMode::FailIfExists`
+/// an existing file is never overwritten.
+pub fn serialize_struct_to_cbor_with_mode(
+    struct_data: &University,
+    filename: &str,
+    mode: WriteMode,
+) -> Result<(), Error> {
+    let claim = mode.claim(filename)?;
+    serialize_struct_to_cbor(struct_data, filename);
+    claim.keep();
+    Ok(())
+}
+
+pub fn serialize_struct_to_msgpack(struct_data: &University, filename: &str) {
+    write_encoded(filename, Format::MessagePack, struct_data);
+}
+
+pub fn deserialize_struct_from_msgpack(filename: &str) -> University {
+    read_encoded(filename, rmp_serde::decode::from_read)
+}
+
+/// Same as serialize_struct_to_msgpack, but with `WriteMode::FailIfExists`
+/// an existing file is never overwritten.
+pub fn serialize_struct_to_msgpack_with_mode(
+    struct_data: &University,
+    filename: &str,
+    mode: WriteMode,
+) -> Result<(), Error> {
+    let claim = mode.claim(filename)?;
+    serialize_struct_to_msgpack(struct_data, filename);
+    claim.keep();
+    Ok(())
+}
+
+/// Opens `filename` for writing and fills it through a buffered writer using
+/// `format`'s encoder, the same one `Format::encode` uses.
+fn write_encoded(filename: &str, format: Format, struct_data: &University) {
+    let f = File::create(filename).expect("error creating file");
+    let mut writer = BufWriter::new(f);
+    format
+        .encode_into(&mut writer, struct_data)
+        .expect("error encoding struct");
+    writer.flush().expect("error writing file");
+}
+
+/// Opens `filename` for reading and lets `decode` parse it through a buffered reader.
+fn read_encoded<E: std::fmt::Debug>(
+    filename: &str,
+    decode: impl FnOnce(BufReader<File>) -> Result<University, E>,
+) -> University {
+    let f = File::open(filename).expect("error while opening file");
+    decode(BufReader::new(f)).expect("error decoding struct")
+}
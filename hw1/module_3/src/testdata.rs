@@ -0,0 +1,48 @@
+use rand::distributions::{Alphanumeric, Standard};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Error;
+use std::path::Path;
+
+use crate::hashmap::serialize_data_to_disk;
+use crate::vector::serialize_iter_to_disk;
+
+/// Name of the vector file generate_dataset writes inside its directory
+pub const VECTOR_FILENAME: &str = "vector.bin";
+
+/// Name of the hashmap file generate_dataset writes inside its directory
+pub const HASHMAP_FILENAME: &str = "hashmap.bin";
+
+/// Writes a vector file and a hashmap file of random data into `dir`, creating
+/// it if needed. The data is drawn from an RNG seeded with `seed`, so the same
+/// seed always produces the same data. The vector file is byte-for-byte
+/// reproducible; the hashmap file holds the same entries, but in whatever
+/// order serialize_data_to_disk writes them, which can differ between runs.
+pub fn generate_dataset(
+    dir: &str,
+    seed: u64,
+    vector_len: usize,
+    map_entries: usize,
+) -> Result<(), Error> {
+    fs::create_dir_all(dir)?;
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let vector_path = Path::new(dir).join(VECTOR_FILENAME);
+    let values = (0..vector_len).map(|_| rng.sample(Standard));
+    serialize_iter_to_disk(values, &vector_path.to_string_lossy())?;
+
+    let mut map = HashMap::with_capacity(map_entries);
+    while map.len() < map_entries {
+        let key_len = rng.gen_range(10..20);
+        let key: String = (&mut rng)
+            .sample_iter(&Alphanumeric)
+            .take(key_len)
+            .map(char::from)
+            .collect();
+        map.insert(key, rng.gen_range(0..20000));
+    }
+    let map_path = Path::new(dir).join(HASHMAP_FILENAME);
+    serialize_data_to_disk(map, &map_path.to_string_lossy())
+}
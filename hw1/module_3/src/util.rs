@@ -0,0 +1,251 @@
+use sha2::Digest;
+use std::fs;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, BufWriter, Error, ErrorKind, Read, Write};
+
+/// Marks the start of a file that begins with a `ChecksumHeader`
+const CHECKSUM_MAGIC: [u8; 4] = *b"CSUM";
+
+/// Size of the chunks hashers pull from their reader
+const CHECKSUM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// How a serializer treats a file that already exists at its target path
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WriteMode {
+    /// Replace whatever is there, like `File::create`
+    Overwrite,
+    /// Leave an existing file alone and fail with `ErrorKind::AlreadyExists`
+    FailIfExists,
+}
+
+impl WriteMode {
+    /// Checks that `filename` may be written under this mode. For `FailIfExists`
+    /// the file is created here with `create_new`, so an existing file is never
+    /// touched; the serializer then reopens the path to write the contents.
+    /// The returned `Claim` removes the file again unless `keep` is called.
+    pub fn claim(self, filename: &str) -> Result<Claim, Error> {
+        match self {
+            WriteMode::Overwrite => Ok(Claim { path: None }),
+            WriteMode::FailIfExists => {
+                OpenOptions::new()
+                    .write(true)
+                    .create_new(true)
+                    .open(filename)?;
+                Ok(Claim {
+                    path: Some(filename.to_string()),
+                })
+            }
+        }
+    }
+}
+
+/// A file created by `WriteMode::claim`. If it is dropped before `keep`, for
+/// example because the serializer failed or panicked, the file is removed so
+/// later `FailIfExists` writes to the same path can still succeed.
+#[must_use]
+pub struct Claim {
+    path: Option<String>,
+}
+
+impl Claim {
+    /// Keeps the claimed file once it has been written successfully
+    pub fn keep(mut self) {
+        self.path = None;
+    }
+}
+
+impl Drop for Claim {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// A checksum algorithm used to stamp and verify checksummed files
+pub trait Hasher {
+    /// Tag stored in the file header recording which algorithm made the checksum
+    fn id(&self) -> u8;
+
+    /// Computes the checksum of everything `reader` yields
+    fn checksum(&self, reader: &mut dyn Read) -> Result<Vec<u8>, Error>;
+}
+
+/// CRC-32 (IEEE), a 4-byte checksum that is quick to compute
+pub struct Crc32;
+
+/// SHA-256, a 32-byte cryptographic digest
+pub struct Sha256;
+
+impl Hasher for Crc32 {
+    fn id(&self) -> u8 {
+        1
+    }
+
+    fn checksum(&self, reader: &mut dyn Read) -> Result<Vec<u8>, Error> {
+        let mut hasher = crc32fast::Hasher::new();
+        for_each_chunk(reader, |chunk| hasher.update(chunk))?;
+        Ok(hasher.finalize().to_be_bytes().to_vec())
+    }
+}
+
+impl Hasher for Sha256 {
+    fn id(&self) -> u8 {
+        2
+    }
+
+    fn checksum(&self, reader: &mut dyn Read) -> Result<Vec<u8>, Error> {
+        let mut hasher = sha2::Sha256::new();
+        for_each_chunk(reader, |chunk| hasher.update(chunk))?;
+        Ok(hasher.finalize().to_vec())
+    }
+}
+
+/// Looks up the hasher that a checksum header's algorithm tag refers to
+pub fn hasher_for_id(id: u8) -> Option<&'static dyn Hasher> {
+    match id {
+        1 => Some(&Crc32),
+        2 => Some(&Sha256),
+        _ => None,
+    }
+}
+
+fn for_each_chunk(reader: &mut dyn Read, mut f: impl FnMut(&[u8])) -> Result<(), Error> {
+    let mut buffer = vec![0u8; CHECKSUM_CHUNK_SIZE];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => return Ok(()),
+            Ok(n) => f(&buffer[..n]),
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Header at the start of a checksummed file: the magic bytes, the algorithm
+/// tag, the checksum length, then the checksum of the payload that follows.
+struct ChecksumHeader {
+    algorithm: u8,
+    checksum: Vec<u8>,
+}
+
+impl ChecksumHeader {
+    fn write_to(&self, writer: &mut impl Write) -> Result<(), Error> {
+        writer.write_all(&CHECKSUM_MAGIC)?;
+        writer.write_all(&[self.algorithm, self.checksum.len() as u8])?;
+        writer.write_all(&self.checksum)
+    }
+
+    fn read_from(reader: &mut impl Read) -> Result<Self, Error> {
+        let mut fixed = [0u8; 6];
+        reader.read_exact(&mut fixed)?;
+        if fixed[..4] != CHECKSUM_MAGIC {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "file does not start with a checksum header",
+            ));
+        }
+        let mut checksum = vec![0u8; fixed[5] as usize];
+        reader.read_exact(&mut checksum)?;
+        Ok(ChecksumHeader {
+            algorithm: fixed[4],
+            checksum,
+        })
+    }
+
+    /// Errors unless the header was stamped by `hasher`
+    fn expect_algorithm(&self, hasher: &dyn Hasher) -> Result<(), Error> {
+        if self.algorithm != hasher.id() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "file was stamped with algorithm {} but {} was given",
+                    self.algorithm,
+                    hasher.id()
+                ),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Writes `payload` to `filename` behind a header holding its checksum
+pub fn write_checksummed(payload: &[u8], filename: &str, hasher: &dyn Hasher) -> Result<(), Error> {
+    let header = ChecksumHeader {
+        algorithm: hasher.id(),
+        checksum: hasher.checksum(&mut &payload[..])?,
+    };
+    let f = File::create(filename)?;
+    let mut writer = BufWriter::new(f);
+    header.write_to(&mut writer)?;
+    writer.write_all(payload)?;
+    writer.flush()
+}
+
+/// Reads the payload of a checksummed file, erroring if it does not match its checksum
+pub fn read_checksummed(filename: &str, hasher: &dyn Hasher) -> Result<Vec<u8>, Error> {
+    let mut reader = BufReader::new(File::open(filename)?);
+    let header = ChecksumHeader::read_from(&mut reader)?;
+    header.expect_algorithm(hasher)?;
+    read_verified_payload(&mut reader, &header, hasher)
+}
+
+/// Reads the rest of `reader` as the payload and checks it against `header`
+fn read_verified_payload(
+    reader: &mut impl Read,
+    header: &ChecksumHeader,
+    hasher: &dyn Hasher,
+) -> Result<Vec<u8>, Error> {
+    let mut payload = Vec::new();
+    reader.read_to_end(&mut payload)?;
+    if hasher.checksum(&mut &payload[..])? != header.checksum {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "payload does not match its checksum",
+        ));
+    }
+    Ok(payload)
+}
+
+/// Checks a checksummed file against its stored checksum while streaming the
+/// payload through `hasher`, so memory use does not grow with the file.
+/// A payload that does not match is reported as `Ok(false)`.
+pub fn verify_file_streaming(filename: &str, hasher: &dyn Hasher) -> Result<bool, Error> {
+    let mut reader = BufReader::new(File::open(filename)?);
+    let header = ChecksumHeader::read_from(&mut reader)?;
+    header.expect_algorithm(hasher)?;
+    Ok(hasher.checksum(&mut reader)? == header.checksum)
+}
+
+/// Replaces the checksum of a checksummed file with one computed by `new_hasher`.
+/// The old checksum is verified first, and the payload bytes are copied over as-is.
+pub fn restamp_checksum(filename: &str, new_hasher: &dyn Hasher) -> Result<(), Error> {
+    let mut reader = BufReader::new(File::open(filename)?);
+    let header = ChecksumHeader::read_from(&mut reader)?;
+    let old_hasher = hasher_for_id(header.algorithm).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidData,
+            format!("unknown checksum algorithm {}", header.algorithm),
+        )
+    })?;
+    let payload = read_verified_payload(&mut reader, &header, old_hasher)?;
+
+    let new_header = ChecksumHeader {
+        algorithm: new_hasher.id(),
+        checksum: new_hasher.checksum(&mut &payload[..])?,
+    };
+    // Write the new file beside the old one and swap it in, so a failure
+    // part way through never leaves a half-written file behind. Claiming the
+    // temp name first means an existing file that happens to have it is never
+    // clobbered, and the claim removes the temp file again if a write fails.
+    let tmp_filename = format!("{}.restamp", filename);
+    let claim = WriteMode::FailIfExists.claim(&tmp_filename)?;
+    let mut writer = BufWriter::new(File::create(&tmp_filename)?);
+    new_header.write_to(&mut writer)?;
+    writer.write_all(&payload)?;
+    writer.flush()?;
+    drop(writer);
+    fs::rename(&tmp_filename, filename)?;
+    claim.keep();
+    Ok(())
+}
@@ -1,6 +1,20 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs;
 use std::fs::File;
 use std::io::Error;
-use std::io::{BufReader, Read, Write};
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Seek, SeekFrom, Write};
+
+use crate::util::WriteMode;
+
+/// Width in bytes of one serialized vector element (a big-endian 4-byte integer)
+const ELEMENT_WIDTH: usize = 4;
+
+/// Width in bytes of the element count written after a vector by serialize_vector_with_footer
+const FOOTER_SIZE: usize = 8;
+
+/// Marks the start of a vector file that begins with a `VectorHeader`
+const HEADER_MAGIC: [u8; 4] = *b"VECH";
 
 pub fn serialize_vector_to_disk(data: Vec<i32>, filename: &str) -> Result<(), Error> {
     panic!("TODO: Complete this Code Segment");
@@ -9,3 +23,477 @@ pub fn serialize_vector_to_disk(data: Vec<i32>, filename: &str) -> Result<(), Er
 pub fn deserialize_vector_from_disk(filename: &str) -> Vec<i32> {
     panic!("TODO: Complete this Code Segment");
 }
+
+/// Same as serialize_vector_to_disk, but with `WriteMode::FailIfExists`
+/// an existing file is never overwritten.
+pub fn serialize_vector_to_disk_with_mode(
+    data: Vec<i32>,
+    filename: &str,
+    mode: WriteMode,
+) -> Result<(), Error> {
+    let claim = mode.claim(filename)?;
+    serialize_vector_to_disk(data, filename)?;
+    claim.keep();
+    Ok(())
+}
+
+/// Serializes elements as the iterator produces them, without collecting them
+/// into a vector first. Returns how many elements were written.
+pub fn serialize_iter_to_disk<I: Iterator<Item = u32>>(
+    iter: I,
+    filename: &str,
+) -> Result<usize, Error> {
+    let f = File::create(filename)?;
+    let mut writer = BufWriter::new(f);
+    let mut count = 0;
+    for x in iter {
+        writer.write_all(&x.to_be_bytes())?;
+        count += 1;
+    }
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Opens a serialized vector and streams its elements back one at a time,
+/// reading `buffer_elements` elements from the file per underlying read.
+/// Elements are decoded as u32, so this is meant for files written by
+/// serialize_iter_to_disk; an i32 file from serialize_vector_to_disk reads
+/// its negative values back as large positive ones.
+pub fn deserialize_vector_stream(
+    filename: &str,
+    buffer_elements: usize,
+) -> Result<VectorStream<File>, Error> {
+    let f = File::open(filename)?;
+    VectorStream::new(f, buffer_elements)
+}
+
+/// Iterator over the u32 elements of a serialized vector that only holds
+/// one buffer's worth of elements in memory at a time.
+pub struct VectorStream<R: Read> {
+    reader: R,
+    buffer: Vec<u8>,
+    pos: usize,
+    len: usize,
+    done: bool,
+}
+
+impl<R: Read> VectorStream<R> {
+    pub fn new(reader: R, buffer_elements: usize) -> Result<Self, Error> {
+        if buffer_elements == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "buffer must hold at least one element",
+            ));
+        }
+        Ok(VectorStream {
+            reader,
+            buffer: vec![0; buffer_elements * ELEMENT_WIDTH],
+            pos: 0,
+            len: 0,
+            done: false,
+        })
+    }
+
+    /// Fills the buffer as far as the reader allows. The buffer only ends up
+    /// partially filled at the end of the file, so elements never straddle refills.
+    fn refill(&mut self) -> Result<(), Error> {
+        self.pos = 0;
+        self.len = 0;
+        while self.len < self.buffer.len() {
+            match self.reader.read(&mut self.buffer[self.len..]) {
+                Ok(0) => break,
+                Ok(n) => self.len += n,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for VectorStream<R> {
+    type Item = Result<u32, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.pos == self.len {
+            if let Err(e) = self.refill() {
+                self.done = true;
+                return Some(Err(e));
+            }
+            if self.len == 0 {
+                self.done = true;
+                return None;
+            }
+        }
+        if self.len - self.pos < ELEMENT_WIDTH {
+            self.done = true;
+            return Some(Err(Error::new(
+                ErrorKind::UnexpectedEof,
+                "file ends in the middle of an element",
+            )));
+        }
+        let mut bytes = [0u8; ELEMENT_WIDTH];
+        bytes.copy_from_slice(&self.buffer[self.pos..self.pos + ELEMENT_WIDTH]);
+        self.pos += ELEMENT_WIDTH;
+        Some(Ok(u32::from_be_bytes(bytes)))
+    }
+}
+
+/// Number of elements the streaming helpers read from disk at a time
+const STREAM_BUFFER_ELEMENTS: usize = 1024;
+
+/// Finds the `k` largest elements of a serialized vector, largest first, while
+/// only ever keeping `k` of them in memory. Files with fewer than `k` elements
+/// return all of them.
+pub fn top_k_stream(filename: &str, k: usize) -> Result<Vec<u32>, Error> {
+    if k == 0 {
+        return Ok(Vec::new());
+    }
+    // Min-heap of the best k seen so far; the root is the one to evict next.
+    // It grows as elements arrive, so a `k` far beyond the file size costs nothing.
+    let mut heap: BinaryHeap<Reverse<u32>> = BinaryHeap::new();
+    for x in deserialize_vector_stream(filename, STREAM_BUFFER_ELEMENTS)? {
+        let x = x?;
+        if heap.len() < k {
+            heap.push(Reverse(x));
+        } else if heap.peek().is_some_and(|&Reverse(smallest)| x > smallest) {
+            heap.pop();
+            heap.push(Reverse(x));
+        }
+    }
+    // Ascending order of Reverse is descending order of the values.
+    Ok(heap
+        .into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(x)| x)
+        .collect())
+}
+
+/// Writes the elements of two equally long plain vector files alternately into
+/// `output` (a0, b0, a1, b1, ...), streaming both inputs. Returns the number
+/// of elements written. Headered files are rejected, and `output` is only
+/// replaced once every element has been written.
+pub fn interleave_vector_files(a: &str, b: &str, output: &str) -> Result<usize, Error> {
+    for input in [a, b] {
+        if starts_with_header(&mut File::open(input)?)? {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "{} has a vector header; only plain vector files can be interleaved",
+                    input
+                ),
+            ));
+        }
+    }
+    let (len_a, len_b) = (count_vector_elements(a)?, count_vector_elements(b)?);
+    if len_a != len_b {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "cannot interleave {} ({} elements) with {} ({} elements)",
+                a, len_a, b, len_b
+            ),
+        ));
+    }
+    // Write beside `output` and swap it in, so a failure part way through
+    // never leaves a half-written file behind.
+    let tmp_output = format!("{}.interleave", output);
+    match write_interleaved(a, b, &tmp_output) {
+        Ok(count) => {
+            fs::rename(&tmp_output, output)?;
+            Ok(count)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&tmp_output);
+            Err(e)
+        }
+    }
+}
+
+fn write_interleaved(a: &str, b: &str, output: &str) -> Result<usize, Error> {
+    let stream_a = deserialize_vector_stream(a, STREAM_BUFFER_ELEMENTS)?;
+    let stream_b = deserialize_vector_stream(b, STREAM_BUFFER_ELEMENTS)?;
+    let mut writer = BufWriter::new(File::create(output)?);
+    let mut count = 0;
+    for (x, y) in stream_a.zip(stream_b) {
+        writer.write_all(&x?.to_be_bytes())?;
+        writer.write_all(&y?.to_be_bytes())?;
+        count += 2;
+    }
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Streams a serialized vector into a single-column CSV file with one value
+/// per line and no header row, so an empty vector gives an empty file.
+/// Returns the number of values written. Values are read with
+/// deserialize_vector_stream, so only u32 files are supported.
+pub fn export_vector_to_csv(filename_in: &str, filename_out: &str) -> Result<usize, Error> {
+    let stream = deserialize_vector_stream(filename_in, STREAM_BUFFER_ELEMENTS)?;
+    let mut writer = BufWriter::new(File::create(filename_out)?);
+    let mut count = 0;
+    for x in stream {
+        writeln!(writer, "{}", x?)?;
+        count += 1;
+    }
+    writer.flush()?;
+    Ok(count)
+}
+
+/// Describes the contents of a headered vector file: the magic bytes,
+/// then the width of each element in bytes, then the number of elements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VectorHeader {
+    pub element_width: u8,
+    pub count: u64,
+}
+
+impl VectorHeader {
+    /// Number of bytes the header takes up at the start of the file
+    pub const SIZE: usize = HEADER_MAGIC.len() + 1 + 8;
+
+    fn write_to(&self, writer: &mut impl Write) -> Result<(), Error> {
+        writer.write_all(&HEADER_MAGIC)?;
+        writer.write_all(&[self.element_width])?;
+        writer.write_all(&self.count.to_be_bytes())
+    }
+
+    /// Errors unless the file's elements are `expected_width` bytes wide
+    fn expect_width(&self, expected_width: u8) -> Result<(), Error> {
+        if self.element_width != expected_width {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "expected {}-byte elements but file stores {}-byte elements",
+                    expected_width, self.element_width
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Errors unless the `file_len`-byte file holds exactly `count` elements after the header
+    fn expect_payload_len(&self, file_len: u64) -> Result<(), Error> {
+        let payload_len = file_len - Self::SIZE as u64;
+        if self.count.checked_mul(self.element_width as u64) != Some(payload_len) {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "header promises {} elements but the file holds {} bytes of them",
+                    self.count, payload_len
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    fn read_from(reader: &mut impl Read) -> Result<Self, Error> {
+        let mut bytes = [0u8; Self::SIZE];
+        reader.read_exact(&mut bytes)?;
+        if bytes[..4] != HEADER_MAGIC {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "file does not start with a vector header",
+            ));
+        }
+        let mut count = [0u8; 8];
+        count.copy_from_slice(&bytes[5..]);
+        Ok(VectorHeader {
+            element_width: bytes[4],
+            count: u64::from_be_bytes(count),
+        })
+    }
+}
+
+/// An integer type that can be stored in a headered vector file
+pub trait VectorElement: Sized {
+    /// Number of bytes one element takes on disk
+    const WIDTH: u8;
+
+    fn write_be(&self, writer: &mut impl Write) -> Result<(), Error>;
+
+    /// Decodes one element from exactly `WIDTH` big-endian bytes
+    fn from_be_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_vector_element {
+    ($($t:ty),*) => {
+        $(
+            impl VectorElement for $t {
+                const WIDTH: u8 = std::mem::size_of::<$t>() as u8;
+
+                fn write_be(&self, writer: &mut impl Write) -> Result<(), Error> {
+                    writer.write_all(&self.to_be_bytes())
+                }
+
+                fn from_be_slice(bytes: &[u8]) -> Self {
+                    <$t>::from_be_bytes(bytes.try_into().expect("slice is one element wide"))
+                }
+            }
+        )*
+    };
+}
+
+impl_vector_element!(u32, i32, u64, i64);
+
+/// Serializes a vector behind a header that records its element width and length
+pub fn serialize_vector_with_header<T: VectorElement>(
+    data: &[T],
+    filename: &str,
+) -> Result<(), Error> {
+    let f = File::create(filename)?;
+    let mut writer = BufWriter::new(f);
+    let header = VectorHeader {
+        element_width: T::WIDTH,
+        count: data.len() as u64,
+    };
+    header.write_to(&mut writer)?;
+    for x in data {
+        x.write_be(&mut writer)?;
+    }
+    writer.flush()
+}
+
+/// Reads just the header of a headered vector file
+pub fn read_vector_header(filename: &str) -> Result<VectorHeader, Error> {
+    let mut f = File::open(filename)?;
+    VectorHeader::read_from(&mut f)
+}
+
+/// Errors if a headered vector file was not written with `expected_width`-byte
+/// elements, so a file is never decoded as the wrong integer type.
+pub fn assert_vector_element_width(filename: &str, expected_width: u8) -> Result<(), Error> {
+    read_vector_header(filename)?.expect_width(expected_width)
+}
+
+/// Deserializes a headered vector file. The vector is allocated once with
+/// exactly the element count stored in the header, so it never reallocates.
+pub fn deserialize_vector_with_header<T: VectorElement>(filename: &str) -> Result<Vec<T>, Error> {
+    let f = File::open(filename)?;
+    let file_len = f.metadata()?.len();
+    let mut reader = BufReader::new(f);
+    let header = VectorHeader::read_from(&mut reader)?;
+    header.expect_width(T::WIDTH)?;
+    // Check the header against the file before trusting its count for the allocation.
+    header.expect_payload_len(file_len)?;
+
+    let mut data = Vec::with_capacity(header.count as usize);
+    let mut bytes = vec![0u8; T::WIDTH as usize];
+    for _ in 0..header.count {
+        reader.read_exact(&mut bytes)?;
+        data.push(T::from_be_slice(&bytes));
+    }
+    Ok(data)
+}
+
+/// Serializes a vector followed by a footer holding its element count as a
+/// big-endian u64, so readers can tell if elements went missing.
+pub fn serialize_vector_with_footer(data: &[u32], filename: &str) -> Result<(), Error> {
+    let f = File::create(filename)?;
+    let mut writer = BufWriter::new(f);
+    for x in data {
+        writer.write_all(&x.to_be_bytes())?;
+    }
+    writer.write_all(&(data.len() as u64).to_be_bytes())?;
+    writer.flush()
+}
+
+/// Deserializes a vector written by serialize_vector_with_footer, erroring if
+/// the number of elements read does not match the count in the footer.
+pub fn deserialize_vector_with_footer(filename: &str) -> Result<Vec<u32>, Error> {
+    let mut bytes = Vec::new();
+    BufReader::new(File::open(filename)?).read_to_end(&mut bytes)?;
+    if bytes.len() < FOOTER_SIZE {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "file is too short to hold a footer",
+        ));
+    }
+    let (payload, footer) = bytes.split_at(bytes.len() - FOOTER_SIZE);
+    let expected = u64::from_be_bytes(footer.try_into().expect("footer is 8 bytes"));
+    if payload.len() % ELEMENT_WIDTH != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "file ends in the middle of an element",
+        ));
+    }
+    let data: Vec<u32> = payload
+        .chunks_exact(ELEMENT_WIDTH)
+        .map(|chunk| u32::from_be_bytes(chunk.try_into().expect("chunk is one element wide")))
+        .collect();
+    if data.len() as u64 != expected {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "footer promises {} elements but {} were read",
+                expected,
+                data.len()
+            ),
+        ));
+    }
+    Ok(data)
+}
+
+/// Counts the elements of a serialized vector without reading them. Headered
+/// files report the count stored in their header, which must agree with the
+/// file length; plain files (as written by
+/// serialize_vector_to_disk) are measured by length and must hold whole elements.
+/// A plain file whose first bytes happen to spell the header magic is taken as headered.
+pub fn count_vector_elements(filename: &str) -> Result<usize, Error> {
+    let mut f = File::open(filename)?;
+    let file_len = f.metadata()?.len();
+    if starts_with_header(&mut f)? {
+        let header = VectorHeader::read_from(&mut f)?;
+        header.expect_payload_len(file_len)?;
+        return Ok(header.count as usize);
+    }
+    if file_len % ELEMENT_WIDTH as u64 != 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "file length {} is not a multiple of the {}-byte element width",
+                file_len, ELEMENT_WIDTH
+            ),
+        ));
+    }
+    Ok((file_len / ELEMENT_WIDTH as u64) as usize)
+}
+
+/// Whether `f` is long enough for a `VectorHeader` and starts with its magic.
+/// Leaves `f` positioned at its start either way.
+fn starts_with_header(f: &mut File) -> Result<bool, Error> {
+    if f.metadata()?.len() < VectorHeader::SIZE as u64 {
+        return Ok(false);
+    }
+    let mut magic = [0u8; HEADER_MAGIC.len()];
+    f.read_exact(&mut magic)?;
+    f.seek(SeekFrom::Start(0))?;
+    Ok(magic == HEADER_MAGIC)
+}
+
+/// Averages every run of `window` consecutive elements, producing
+/// `data.len() - window + 1` values. A window longer than the data yields
+/// an empty vector; a window of zero is an error.
+pub fn moving_average(data: &[u32], window: usize) -> Result<Vec<f64>, Error> {
+    if window == 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "window must hold at least one element",
+        ));
+    }
+    if window > data.len() {
+        return Ok(Vec::new());
+    }
+    let mut sum: u64 = data[..window].iter().map(|&x| x as u64).sum();
+    let mut averages = Vec::with_capacity(data.len() - window + 1);
+    averages.push(sum as f64 / window as f64);
+    for i in window..data.len() {
+        sum += data[i] as u64;
+        sum -= data[i - window] as u64;
+        averages.push(sum as f64 / window as f64);
+    }
+    Ok(averages)
+}
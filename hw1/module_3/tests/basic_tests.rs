@@ -1,4 +1,7 @@
-use module_3::basic::{deserialize_from_bytes, serialize_to_bytes, serialize_to_string};
+use module_3::basic::{
+    Radix, deserialize_from_bytes, deserialize_from_string_radix, serialize_to_bytes,
+    serialize_to_string, serialize_to_string_radix,
+};
 
 #[test]
 fn check_serialize_to_string() {
@@ -20,3 +23,26 @@ fn check_deserialize_from_bytes() {
     let integer_deser = deserialize_from_bytes(integer.to_be_bytes());
     assert_eq!(integer_deser, integer);
 }
+
+#[test]
+fn check_serialize_to_string_radix() {
+    let integer: u32 = 255;
+    assert_eq!(serialize_to_string_radix(integer, Radix::Dec), "255");
+    assert_eq!(serialize_to_string_radix(integer, Radix::Hex), "0xff");
+    assert_eq!(serialize_to_string_radix(integer, Radix::Bin), "0b11111111");
+    assert_eq!(serialize_to_string_radix(integer, Radix::Oct), "0o377");
+
+    for radix in [Radix::Dec, Radix::Hex, Radix::Bin, Radix::Oct] {
+        let string = serialize_to_string_radix(integer, radix);
+        assert_eq!(deserialize_from_string_radix(&string).unwrap(), integer);
+    }
+}
+
+#[test]
+fn check_serialize_to_string_radix_decimal() {
+    let integer: u32 = 2147483647;
+    assert_eq!(
+        serialize_to_string_radix(integer, Radix::Dec),
+        serialize_to_string(integer)
+    );
+}
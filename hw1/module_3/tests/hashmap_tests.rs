@@ -1,6 +1,10 @@
-use module_3::hashmap::{deserialize_data_from_disk, serialize_data_to_disk};
+use module_3::hashmap::{
+    clamp_values, deserialize_data_from_disk, deserialize_data_rekeyed, load_and_clamp,
+    serialize_data_to_disk, validate_keys,
+};
 use rand::{Rng, distributions::Alphanumeric};
 use std::collections::HashMap;
+use std::io::ErrorKind;
 
 #[test]
 fn test_serialize_deserialize_data_to_disk() {
@@ -20,6 +24,85 @@ fn test_serialize_deserialize_data_to_disk() {
     assert_eq!(return_map == test_map, true);
 }
 
+#[test]
+fn test_deserialize_data_rekeyed() {
+    let filename = "hashmap_rekey_test.bin";
+    let test_map: HashMap<String, i32> = HashMap::from([
+        ("mercury".to_string(), 4),
+        ("venus".to_string(), 7),
+        ("Earth".to_string(), 0),
+        ("earth".to_string(), 3),
+    ]);
+    serialize_data_to_disk(test_map.clone(), filename).unwrap();
+
+    let identity = deserialize_data_rekeyed(filename, |k| k.to_string()).unwrap();
+    assert_eq!(identity, deserialize_data_from_disk(filename));
+
+    let upper = deserialize_data_rekeyed(filename, |k| k.to_uppercase()).unwrap();
+    let expected: HashMap<String, i32> = HashMap::from([
+        ("MERCURY".to_string(), 4),
+        ("VENUS".to_string(), 7),
+        // "earth" sorts after "Earth", so its value wins the collision
+        ("EARTH".to_string(), 3),
+    ]);
+    assert_eq!(upper, expected);
+}
+
+#[test]
+fn test_validate_keys() {
+    let filename = "hashmap_validate_keys_test.bin";
+    let test_map: HashMap<String, i32> = HashMap::from([
+        ("Mercury".to_string(), 4),
+        ("Venus".to_string(), 7),
+        ("Earth 2".to_string(), 0),
+    ]);
+    serialize_data_to_disk(test_map, filename).unwrap();
+
+    assert_eq!(
+        validate_keys(filename, "^[A-Za-z0-9]+$").unwrap(),
+        vec!["Earth 2"]
+    );
+    assert!(
+        validate_keys(filename, "^[A-Za-z0-9 ]+$")
+            .unwrap()
+            .is_empty()
+    );
+
+    let err = validate_keys(filename, "([a-z]").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_load_and_clamp() {
+    let filename = "hashmap_clamp_test.bin";
+    let test_map: HashMap<String, i32> = HashMap::from([
+        ("Mercury".to_string(), -40),
+        ("Venus".to_string(), 7),
+        ("Earth".to_string(), 0),
+        ("Mars".to_string(), 250),
+    ]);
+    serialize_data_to_disk(test_map, filename).unwrap();
+
+    let clamped = load_and_clamp(filename, 0, 100).unwrap();
+    let expected: HashMap<String, i32> = HashMap::from([
+        ("Mercury".to_string(), 0),
+        ("Venus".to_string(), 7),
+        ("Earth".to_string(), 0),
+        ("Mars".to_string(), 100),
+    ]);
+    assert_eq!(clamped, expected);
+
+    let err = load_and_clamp(filename, 10, 5).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+#[should_panic]
+fn clamp_values_empty_range() {
+    let mut map: HashMap<String, i32> = HashMap::from([("Venus".to_string(), 7)]);
+    clamp_values(&mut map, 10, 5);
+}
+
 fn generate_rand_string() -> String {
     let mut rng = rand::thread_rng();
     let str_len: usize = rng.gen_range(10..100);
@@ -1,6 +1,8 @@
 use module_3::structure::{
-    University, deserialize_jsonstring_to_struct, deserialize_struct_from_cbor,
-    serialize_struct_to_cbor,
+    Format, StructureError, University, deserialize_jsonstring_capturing_unknown,
+    deserialize_jsonstring_to_struct, deserialize_struct_from_cbor,
+    deserialize_struct_from_msgpack, is_cbor_complete, serialize_struct_to_cbor,
+    serialize_struct_to_msgpack, smallest_format, validate_import,
 };
 
 #[test]
@@ -64,3 +66,229 @@ fn test_serialize_deserialize_json_cbor() {
     assert_eq!(uchicago_from_cbor.graduate_enrollment, 50);
     assert_eq!(uchicago_from_cbor.acceptance_rate, 0.07);
 }
+
+#[test]
+fn test_serialize_deserialize_json_msgpack() {
+    let json_string = r#"
+{
+ "name": "University of Chicago",
+ "undergraduate_enrollment": 7559,
+ "graduate_enrollment": 10893,
+ "schools": ["Divinity School", "Law School"],
+ "acceptance_rate": 0.07
+}"#;
+
+    let uchicago: University = deserialize_jsonstring_to_struct(json_string);
+    let filename = "uchicago_test.msgpack";
+    serialize_struct_to_msgpack(&uchicago, filename);
+    let uchicago_from_msgpack: University = deserialize_struct_from_msgpack(filename);
+
+    assert_eq!(uchicago_from_msgpack.undergraduate_enrollment, 7559);
+    assert_eq!(uchicago_from_msgpack.graduate_enrollment, 10893);
+    assert_eq!(uchicago_from_msgpack.acceptance_rate, 0.07);
+
+    let msgpack_size = std::fs::metadata(filename).unwrap().len() as usize;
+    assert!(msgpack_size < Format::Cbor.encode(&uchicago).len());
+}
+
+#[test]
+fn test_acceptance_rate_display() {
+    let mut uchicago = sample_university();
+    assert_eq!(uchicago.acceptance_rate_display(), "7.0%");
+
+    uchicago.acceptance_rate = 0.0;
+    assert_eq!(uchicago.acceptance_rate_display(), "0.0%");
+
+    uchicago.acceptance_rate = 1.0;
+    assert_eq!(uchicago.acceptance_rate_display(), "100.0%");
+}
+
+#[test]
+fn test_school_accessors() {
+    let uchicago = sample_university();
+    let names: Vec<&str> = uchicago.school_names().collect();
+    assert_eq!(
+        names,
+        vec![
+            "Biological Sciences Division",
+            "Divinity School",
+            "Law School",
+            "Physical Sciences Division",
+        ]
+    );
+    assert_eq!(uchicago.school_at(2), Some("Law School"));
+    assert_eq!(uchicago.school_at(4), None);
+}
+
+#[test]
+fn test_add_schools() {
+    let mut uchicago = sample_university();
+    let added = uchicago
+        .add_schools(&["Humanities Division", "Harris School of Public Policy"])
+        .unwrap();
+    assert_eq!(added, 2);
+    assert_eq!(uchicago.schools.len(), 6);
+
+    let err = uchicago
+        .add_schools(&[
+            "Oriental Institute",
+            "law school",
+            "Social Sciences Division",
+        ])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        StructureError::DuplicateSchool {
+            index: 1,
+            name: String::from("law school"),
+        }
+    );
+    assert_eq!(uchicago.school_at(6), Some("Oriental Institute"));
+    assert_eq!(uchicago.schools.len(), 7);
+
+    let err = uchicago.add_schools(&["  "]).unwrap_err();
+    assert_eq!(err, StructureError::EmptySchoolName { index: 0 });
+}
+
+#[test]
+fn test_merge_schools_from() {
+    let mut uchicago = sample_university();
+    let mut other = sample_university();
+    other.schools = vec![
+        String::from("LAW SCHOOL"),
+        String::from("Pritzker School of Medicine"),
+        String::from("Divinity School"),
+        String::from("Humanities Division"),
+    ];
+
+    uchicago.merge_schools_from(&other);
+    let expected = vec![
+        "Biological Sciences Division",
+        "Divinity School",
+        "Law School",
+        "Physical Sciences Division",
+        "Pritzker School of Medicine",
+        "Humanities Division",
+    ];
+    assert_eq!(uchicago.schools, expected);
+
+    uchicago.merge_schools_from(&other);
+    assert_eq!(uchicago.schools, expected);
+}
+
+#[test]
+fn test_enrollment_shares() {
+    let mut uchicago = sample_university();
+    let shares = uchicago.enrollment_shares();
+    assert_eq!(shares.len(), 4);
+    assert_eq!(shares[2].0, "Law School");
+    let total: f64 = shares.iter().map(|(_, share)| share).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+
+    uchicago.schools.clear();
+    assert!(uchicago.enrollment_shares().is_empty());
+}
+
+#[test]
+fn test_validate_import() {
+    let valid = "validate_valid_test.json";
+    let invalid = "validate_invalid_test.json";
+    let missing = "validate_missing_test.json";
+    std::fs::write(
+        valid,
+        r#"{"name": "University of Chicago", "undergraduate_enrollment": 7559,
+            "graduate_enrollment": 10893, "schools": ["Law School", "Divinity School"],
+            "acceptance_rate": 0.07}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        invalid,
+        r#"{"name": " ", "undergraduate_enrollment": 10, "graduate_enrollment": 10,
+            "schools": ["Law School", "law school"], "acceptance_rate": 1.5}"#,
+    )
+    .unwrap();
+    let _ = std::fs::remove_file(missing);
+
+    let report = validate_import(&[valid, invalid, missing]);
+    assert_eq!(report.len(), 3);
+    assert_eq!(report[0], (valid.to_string(), Ok(())));
+    assert_eq!(report[1].0, invalid);
+    let problems = report[1].1.as_ref().unwrap_err();
+    assert_eq!(
+        problems,
+        &vec![
+            String::from("name is empty"),
+            String::from("acceptance_rate 1.5 is not between 0 and 1"),
+            String::from("school 1 (law school) is a duplicate"),
+        ]
+    );
+    assert!(report[2].1.is_err());
+    assert!(!std::path::Path::new(missing).exists());
+}
+
+#[test]
+fn test_deserialize_capturing_unknown_fields() {
+    let json_string = r#"
+{
+ "name": "University of Chicago",
+ "undergraduate_enrollment": 7559,
+ "graduate_enrollment": 10893,
+ "schools": ["Law School"],
+ "acceptance_rate": 0.07,
+ "founded": 1890
+}"#;
+
+    let (uchicago, unknown) = deserialize_jsonstring_capturing_unknown(json_string).unwrap();
+    assert_eq!(uchicago.name, "University of Chicago");
+    assert_eq!(uchicago.graduate_enrollment, 10893);
+    assert_eq!(uchicago.schools, vec!["Law School"]);
+    assert_eq!(uchicago.acceptance_rate, 0.07);
+    assert_eq!(unknown, vec!["founded"]);
+
+    assert!(deserialize_jsonstring_capturing_unknown(r#"{"name": "No Fields"}"#).is_err());
+}
+
+#[test]
+fn test_is_cbor_complete() {
+    let uchicago = sample_university();
+    let filename = "uchicago_complete_test.cbor";
+    serialize_struct_to_cbor(&uchicago, filename);
+    assert!(is_cbor_complete(filename).unwrap());
+
+    let bytes = std::fs::read(filename).unwrap();
+    let truncated = "uchicago_truncated_test.cbor";
+    std::fs::write(truncated, &bytes[..bytes.len() - 5]).unwrap();
+    assert!(!is_cbor_complete(truncated).unwrap());
+
+    let trailing = "uchicago_trailing_test.cbor";
+    let mut extended = bytes.clone();
+    extended.push(0);
+    std::fs::write(trailing, extended).unwrap();
+    assert!(!is_cbor_complete(trailing).unwrap());
+}
+
+#[test]
+fn test_smallest_format() {
+    let uchicago = sample_university();
+    let (format, size) = smallest_format(&uchicago);
+    assert_eq!(format, Format::MessagePack);
+    assert_eq!(size, Format::MessagePack.encode(&uchicago).len());
+    for other in Format::ALL {
+        assert!(other.encode(&uchicago).len() >= size);
+    }
+}
+
+fn sample_university() -> University {
+    University {
+        name: String::from("University of Chicago"),
+        undergraduate_enrollment: 7559,
+        graduate_enrollment: 10893,
+        schools: vec![
+            String::from("Biological Sciences Division"),
+            String::from("Divinity School"),
+            String::from("Law School"),
+            String::from("Physical Sciences Division"),
+        ],
+        acceptance_rate: 0.07,
+    }
+}
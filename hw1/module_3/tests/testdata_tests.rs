@@ -0,0 +1,39 @@
+use module_3::hashmap::deserialize_data_from_disk;
+use module_3::testdata::{HASHMAP_FILENAME, VECTOR_FILENAME, generate_dataset};
+use module_3::vector::deserialize_vector_stream;
+use std::path::PathBuf;
+
+#[test]
+fn test_generate_dataset_reproducible() {
+    let dirs: Vec<PathBuf> = ["a", "b", "c"]
+        .iter()
+        .map(|name| std::env::temp_dir().join(format!("module_3_testdata_{}", name)))
+        .collect();
+    for (dir, seed) in dirs.iter().zip([42, 42, 7]) {
+        generate_dataset(dir.to_str().unwrap(), seed, 500, 50).unwrap();
+    }
+    let read = |dir: &PathBuf, name: &str| std::fs::read(dir.join(name)).unwrap();
+
+    let vector_a = read(&dirs[0], VECTOR_FILENAME);
+    assert_eq!(vector_a, read(&dirs[1], VECTOR_FILENAME));
+    assert_ne!(vector_a, read(&dirs[2], VECTOR_FILENAME));
+
+    let data: Vec<u32> =
+        deserialize_vector_stream(dirs[0].join(VECTOR_FILENAME).to_str().unwrap(), 64)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+    assert_eq!(data.len(), 500);
+
+    // The map file's entry order is up to serialize_data_to_disk, so compare contents
+    let map =
+        |dir: &PathBuf| deserialize_data_from_disk(dir.join(HASHMAP_FILENAME).to_str().unwrap());
+    let map_a = map(&dirs[0]);
+    assert_eq!(map_a.len(), 50);
+    assert_eq!(map_a, map(&dirs[1]));
+    assert_ne!(map_a, map(&dirs[2]));
+
+    for dir in &dirs {
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
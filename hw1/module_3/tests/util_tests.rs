@@ -0,0 +1,66 @@
+use module_3::util::{
+    Crc32, Sha256, WriteMode, read_checksummed, restamp_checksum, verify_file_streaming,
+    write_checksummed,
+};
+
+#[test]
+fn test_restamp_checksum_crc32_to_sha256() {
+    let filename = "checksum_restamp_test.bin";
+    let payload: Vec<u8> = (0..5000u32).flat_map(|x| x.to_be_bytes()).collect();
+    write_checksummed(&payload, filename, &Crc32).unwrap();
+    let before = read_checksummed(filename, &Crc32).unwrap();
+
+    restamp_checksum(filename, &Sha256).unwrap();
+
+    let after = read_checksummed(filename, &Sha256).unwrap();
+    assert_eq!(before, payload);
+    assert_eq!(after, payload);
+    let raw = std::fs::read(filename).unwrap();
+    assert_eq!(&raw[raw.len() - payload.len()..], &payload[..]);
+    assert!(read_checksummed(filename, &Crc32).is_err());
+}
+
+#[test]
+fn test_restamp_checksum_keeps_existing_temp_file() {
+    let filename = "checksum_restamp_clobber_test.bin";
+    let tmp_filename = format!("{}.restamp", filename);
+    write_checksummed(b"payload", filename, &Crc32).unwrap();
+    std::fs::write(&tmp_filename, b"not mine to replace").unwrap();
+
+    let err = restamp_checksum(filename, &Sha256).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    assert_eq!(
+        std::fs::read(&tmp_filename).unwrap(),
+        b"not mine to replace"
+    );
+    assert_eq!(read_checksummed(filename, &Crc32).unwrap(), b"payload");
+    std::fs::remove_file(&tmp_filename).unwrap();
+}
+
+#[test]
+fn test_verify_file_streaming() {
+    let filename = "checksum_streaming_test.bin";
+    let payload: Vec<u8> = (0..4_000_000u32).map(|x| (x % 251) as u8).collect();
+    write_checksummed(&payload, filename, &Sha256).unwrap();
+    assert!(verify_file_streaming(filename, &Sha256).unwrap());
+
+    let mut raw = std::fs::read(filename).unwrap();
+    let last = raw.len() - 1;
+    raw[last] ^= 0xff;
+    std::fs::write(filename, raw).unwrap();
+    assert!(!verify_file_streaming(filename, &Sha256).unwrap());
+}
+
+#[test]
+fn test_dropped_claim_removes_file() {
+    let filename = "claim_drop_test.bin";
+    let _ = std::fs::remove_file(filename);
+
+    drop(WriteMode::FailIfExists.claim(filename).unwrap());
+    assert!(!std::path::Path::new(filename).exists());
+
+    WriteMode::FailIfExists.claim(filename).unwrap().keep();
+    assert!(std::path::Path::new(filename).exists());
+    let err = WriteMode::FailIfExists.claim(filename).err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+}
@@ -1,4 +1,13 @@
-use module_3::vector::{deserialize_vector_from_disk, serialize_vector_to_disk};
+use module_3::util::WriteMode;
+use module_3::vector::{
+    assert_vector_element_width, count_vector_elements, deserialize_vector_from_disk,
+    deserialize_vector_stream, deserialize_vector_with_footer, deserialize_vector_with_header,
+    export_vector_to_csv, interleave_vector_files, moving_average, serialize_iter_to_disk,
+    serialize_vector_to_disk, serialize_vector_to_disk_with_mode, serialize_vector_with_footer,
+    serialize_vector_with_header, top_k_stream,
+};
+use std::fs::File;
+use std::io::{ErrorKind, Write};
 
 #[test]
 fn test_serialize_deserialize_vector_to_disk() {
@@ -17,3 +26,205 @@ fn test_serialize_deserialize_vector_to_disk() {
 
     assert_eq!(n1 as usize, data.len());
 }
+
+#[test]
+fn test_serialize_vector_fail_if_exists() {
+    let filename = "vector_no_clobber_test.bin";
+    let _ = std::fs::remove_file(filename);
+
+    serialize_vector_to_disk_with_mode(vec![1, 2, 3], filename, WriteMode::FailIfExists).unwrap();
+    let err = serialize_vector_to_disk_with_mode(vec![4, 5], filename, WriteMode::FailIfExists)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(deserialize_vector_from_disk(filename), vec![1, 2, 3]);
+
+    serialize_vector_to_disk_with_mode(vec![4, 5], filename, WriteMode::Overwrite).unwrap();
+    assert_eq!(deserialize_vector_from_disk(filename), vec![4, 5]);
+}
+
+#[test]
+fn test_serialize_iter_to_disk() {
+    let filename = "vector_iter_test.bin";
+    let written = serialize_iter_to_disk((0..).take(1000), filename).unwrap();
+    assert_eq!(written, 1000);
+
+    let data: Vec<u32> = deserialize_vector_stream(filename, 64)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(data, (0..1000).collect::<Vec<u32>>());
+}
+
+#[test]
+fn test_deserialize_vector_stream_buffer_sizes() {
+    let filename = "vector_stream_test.bin";
+    let data: Vec<u32> = (0..10).map(|i| i * 7).collect();
+    serialize_iter_to_disk(data.iter().copied(), filename).unwrap();
+
+    let small: Vec<u32> = deserialize_vector_stream(filename, 3)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let large: Vec<u32> = deserialize_vector_stream(filename, 1000)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    assert_eq!(small, data);
+    assert_eq!(large, data);
+}
+
+#[test]
+fn test_deserialize_vector_stream_partial_element() {
+    let filename = "vector_stream_partial_test.bin";
+    let mut f = File::create(filename).unwrap();
+    f.write_all(&[0, 0, 0, 1, 0, 0]).unwrap();
+
+    let mut stream = deserialize_vector_stream(filename, 4).unwrap();
+    assert_eq!(stream.next().unwrap().unwrap(), 1);
+    assert!(stream.next().unwrap().is_err());
+    assert!(stream.next().is_none());
+}
+
+#[test]
+fn test_assert_vector_element_width() {
+    let filename = "vector_width_test.bin";
+    let data: Vec<i64> = vec![1, -2, 3];
+    serialize_vector_with_header(&data, filename).unwrap();
+
+    assert!(assert_vector_element_width(filename, 8).is_ok());
+    let err = assert_vector_element_width(filename, 4).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_top_k_stream() {
+    let filename = "vector_top_k_test.bin";
+    serialize_iter_to_disk(0..1000, filename).unwrap();
+    assert_eq!(
+        top_k_stream(filename, 5).unwrap(),
+        vec![999, 998, 997, 996, 995]
+    );
+
+    let short_filename = "vector_top_k_short_test.bin";
+    serialize_iter_to_disk([3, 9, 1].into_iter(), short_filename).unwrap();
+    assert_eq!(top_k_stream(short_filename, 5).unwrap(), vec![9, 3, 1]);
+    assert_eq!(
+        top_k_stream(short_filename, usize::MAX).unwrap(),
+        vec![9, 3, 1]
+    );
+}
+
+#[test]
+fn test_deserialize_vector_with_header_exact_capacity() {
+    let filename = "vector_header_capacity_test.bin";
+    let data: Vec<u32> = (0..12345).collect();
+    serialize_vector_with_header(&data, filename).unwrap();
+
+    let loaded: Vec<u32> = deserialize_vector_with_header(filename).unwrap();
+    assert_eq!(loaded, data);
+    assert_eq!(loaded.capacity(), loaded.len());
+    assert!(deserialize_vector_with_header::<u64>(filename).is_err());
+}
+
+#[test]
+fn test_count_vector_elements() {
+    let headered = "vector_count_headered_test.bin";
+    serialize_vector_with_header(&[7i64; 42], headered).unwrap();
+    assert_eq!(count_vector_elements(headered).unwrap(), 42);
+
+    let plain = "vector_count_plain_test.bin";
+    serialize_iter_to_disk(0..100, plain).unwrap();
+    assert_eq!(count_vector_elements(plain).unwrap(), 100);
+
+    let truncated = "vector_count_truncated_test.bin";
+    let mut bytes = std::fs::read(plain).unwrap();
+    bytes.pop();
+    std::fs::write(truncated, bytes).unwrap();
+    let err = count_vector_elements(truncated).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    let truncated_headered = "vector_count_truncated_headered_test.bin";
+    let mut bytes = std::fs::read(headered).unwrap();
+    bytes.truncate(bytes.len() - 8);
+    std::fs::write(truncated_headered, bytes).unwrap();
+    let err = count_vector_elements(truncated_headered).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_vector_footer_detects_missing_element() {
+    let filename = "vector_footer_test.bin";
+    let data: Vec<u32> = vec![10, 20, 30, 40];
+    serialize_vector_with_footer(&data, filename).unwrap();
+    assert_eq!(deserialize_vector_with_footer(filename).unwrap(), data);
+
+    // Chop off the last element but leave the footer in place
+    let mut bytes = std::fs::read(filename).unwrap();
+    let footer_start = bytes.len() - 8;
+    bytes.drain(footer_start - 4..footer_start);
+    std::fs::write(filename, bytes).unwrap();
+    let err = deserialize_vector_with_footer(filename).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_interleave_vector_files() {
+    let (a, b, output) = (
+        "vector_interleave_a_test.bin",
+        "vector_interleave_b_test.bin",
+        "vector_interleave_out_test.bin",
+    );
+    serialize_iter_to_disk([0, 2, 4].into_iter(), a).unwrap();
+    serialize_iter_to_disk([1, 3, 5].into_iter(), b).unwrap();
+    assert_eq!(interleave_vector_files(a, b, output).unwrap(), 6);
+    let data: Vec<u32> = deserialize_vector_stream(output, 4)
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(data, vec![0, 1, 2, 3, 4, 5]);
+
+    let short = "vector_interleave_short_test.bin";
+    serialize_iter_to_disk([1, 3].into_iter(), short).unwrap();
+    let err = interleave_vector_files(a, short, output).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_interleave_vector_files_rejects_headered_input() {
+    let (a, b, output) = (
+        "vector_interleave_headered_a_test.bin",
+        "vector_interleave_headered_b_test.bin",
+        "vector_interleave_headered_out_test.bin",
+    );
+    let _ = std::fs::remove_file(output);
+    serialize_vector_with_header(&[0u32, 2, 4], a).unwrap();
+    serialize_vector_with_header(&[1u32, 3, 5], b).unwrap();
+
+    let err = interleave_vector_files(a, b, output).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(!std::path::Path::new(output).exists());
+}
+
+#[test]
+fn test_export_vector_to_csv() {
+    let filename = "vector_csv_test.bin";
+    let csv_filename = "vector_csv_test.csv";
+    serialize_iter_to_disk([1, 2, 3].into_iter(), filename).unwrap();
+    assert_eq!(export_vector_to_csv(filename, csv_filename).unwrap(), 3);
+    let csv = std::fs::read_to_string(csv_filename).unwrap();
+    assert_eq!(csv.lines().collect::<Vec<_>>(), vec!["1", "2", "3"]);
+
+    serialize_iter_to_disk(std::iter::empty(), filename).unwrap();
+    assert_eq!(export_vector_to_csv(filename, csv_filename).unwrap(), 0);
+    assert_eq!(std::fs::read_to_string(csv_filename).unwrap(), "");
+}
+
+#[test]
+fn test_moving_average() {
+    let data = [1, 2, 3, 4];
+    assert_eq!(moving_average(&data, 2).unwrap(), vec![1.5, 2.5, 3.5]);
+    assert_eq!(moving_average(&data, 4).unwrap(), vec![2.5]);
+    assert!(moving_average(&data, 5).unwrap().is_empty());
+    assert!(moving_average(&data, 0).is_err());
+}